@@ -0,0 +1,147 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+/// Result of rewriting a single translation file with [`remove_unused_keys`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FixReport {
+    pub removed: usize,
+}
+
+/// Removes every line assigning one of `keys_to_remove` from `path`, optionally sorting the
+/// remaining lines by key, and writes the result back atomically.
+///
+/// The file is rewritten by creating a temporary file in the same directory as `path`,
+/// flushing and syncing it to disk, then renaming it over the original. This way a reader
+/// never observes a half-written file, even if the process is killed mid-write.
+pub fn remove_unused_keys(
+    path: &Path,
+    keys_to_remove: &HashSet<String>,
+    sort_keys: bool,
+) -> io::Result<FixReport> {
+    let original = std::fs::read_to_string(path)?;
+    let mut removed = 0;
+
+    let mut lines: Vec<&str> = original
+        .lines()
+        .filter(|line| match line_key(line) {
+            Some(key) if keys_to_remove.contains(key) => {
+                removed += 1;
+                false
+            }
+            _ => true,
+        })
+        .collect();
+
+    if sort_keys {
+        sort_key_lines(&mut lines);
+    }
+
+    let mut contents = lines.join("\n");
+    if original.ends_with('\n') {
+        contents.push('\n');
+    }
+
+    write_atomically(path, contents.as_bytes())?;
+
+    Ok(FixReport { removed })
+}
+
+/// Sorts only the key-bearing lines of `lines` by key, leaving comment and blank lines in
+/// their original positions. The sort spans the whole file, so a key can end up under a
+/// different section header than the one it started under.
+fn sort_key_lines(lines: &mut [&str]) {
+    let key_positions: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| line_key(line).map(|_| i))
+        .collect();
+
+    let mut key_lines: Vec<&str> = key_positions.iter().map(|&i| lines[i]).collect();
+    key_lines.sort_by_key(|line| line_key(line).unwrap());
+
+    for (position, line) in key_positions.into_iter().zip(key_lines) {
+        lines[position] = line;
+    }
+}
+
+/// Extracts the `key` part of a `key=value` translation line, or `None` for blank/comment lines.
+fn line_key(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+    trimmed.split_once('=').map(|(key, _)| key.trim())
+}
+
+/// Writes `contents` to `path` without ever leaving a truncated file behind: the data is
+/// written to a temporary file next to `path`, fsynced, then renamed into place.
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let tmp_path = dir.join(format!(
+        ".{}.tmp",
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("tl-check")
+    ));
+
+    let mut tmp_file = File::create(&tmp_path)?;
+    tmp_file.write_all(contents)?;
+    tmp_file.sync_all()?;
+    drop(tmp_file);
+
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `contents` to a fresh temp file and returns its path; the caller removes it.
+    fn write_temp_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let pid = std::process::id();
+        let path = std::env::temp_dir().join(format!("tl-check-test-{pid}-{name}"));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn removes_unused_keys_and_preserves_order() {
+        let path = write_temp_file("order", "# section\nfoo=1\nbar=2\nbaz=3\n");
+        let keys_to_remove: HashSet<String> = ["bar".to_string()].into_iter().collect();
+
+        let report = remove_unused_keys(&path, &keys_to_remove, false).unwrap();
+
+        assert_eq!(report.removed, 1);
+        let result = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(result, "# section\nfoo=1\nbaz=3\n");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn sort_keys_leaves_comments_and_blanks_anchored() {
+        let path = write_temp_file("sort", "# section\nfoo=1\n\nbaz=3\nbar=2\n");
+
+        remove_unused_keys(&path, &HashSet::new(), true).unwrap();
+
+        let result = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(result, "# section\nbar=2\n\nbaz=3\nfoo=1\n");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn preserves_missing_trailing_newline() {
+        let path = write_temp_file("no-newline", "foo=1\nbar=2");
+
+        let report = remove_unused_keys(&path, &HashSet::new(), false).unwrap();
+
+        assert_eq!(report.removed, 0);
+        let result = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(result, "foo=1\nbar=2");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+/// Severity of a single diagnostic finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single machine-readable finding, produced by one of the translation checks.
+///
+/// `rule` is one of `missing-key`, `empty-value`, `invalid-key` or `unused-key`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub rule: &'static str,
+    pub key: String,
+    pub file: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn missing_key(severity: Severity, key: &str, missing_in: &Path) -> Self {
+        Self {
+            severity,
+            rule: "missing-key",
+            key: key.to_string(),
+            file: missing_in.display().to_string(),
+            message: format!("key {key} not found in {}", missing_in.display()),
+        }
+    }
+
+    pub fn empty_value(severity: Severity, key: &str, file: &Path) -> Self {
+        Self {
+            severity,
+            rule: "empty-value",
+            key: key.to_string(),
+            file: file.display().to_string(),
+            message: format!("key {key} seems to be empty"),
+        }
+    }
+
+    pub fn invalid_key(severity: Severity, key: &str, file: &Path) -> Self {
+        Self {
+            severity,
+            rule: "invalid-key",
+            key: key.to_string(),
+            file: file.display().to_string(),
+            message: format!("key {key} does not exist!"),
+        }
+    }
+
+    pub fn unused_key(severity: Severity, key: &str, value: &str, file: &Path) -> Self {
+        Self {
+            severity,
+            rule: "unused-key",
+            key: key.to_string(),
+            file: file.display().to_string(),
+            message: format!("key {key}=\"{value}\" is never used"),
+        }
+    }
+}
+
+/// Renders diagnostics as a single pretty-printed JSON document.
+pub fn to_json(diagnostics: &[Diagnostic]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(diagnostics)
+}
+
+/// Renders diagnostics as a minimal SARIF 2.1.0 log, suitable for GitHub code scanning.
+pub fn to_sarif(diagnostics: &[Diagnostic]) -> serde_json::Result<String> {
+    let results: Vec<_> = diagnostics
+        .iter()
+        .map(|d| {
+            serde_json::json!({
+                "ruleId": d.rule,
+                "level": match d.severity {
+                    Severity::Error => "error",
+                    Severity::Warning => "warning",
+                },
+                "message": { "text": d.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": d.file }
+                    }
+                }]
+            })
+        })
+        .collect();
+
+    let sarif = serde_json::json!({
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "tl-check",
+                    "informationUri": "https://github.com/ponbac/tl-check",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": [
+                        { "id": "missing-key" },
+                        { "id": "empty-value" },
+                        { "id": "invalid-key" },
+                        { "id": "unused-key" },
+                    ]
+                }
+            },
+            "results": results
+        }]
+    });
+
+    serde_json::to_string_pretty(&sarif)
+}
@@ -5,6 +5,9 @@ use nom::{
     IResult,
 };
 
+pub mod autofix;
+pub mod diagnostics;
+pub mod rules;
 pub mod translation_file;
 pub mod ts_file;
 
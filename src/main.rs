@@ -1,12 +1,15 @@
 use std::{collections::HashSet, path::PathBuf};
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use console::style;
+use ignore::{overrides::OverrideBuilder, WalkBuilder};
 use ramilang::{
-    translation_file::{TranslationFile, TranslationFileError},
+    autofix,
+    diagnostics::{self, Diagnostic},
+    rules::{Rule, RulesConfig, Severity as RuleSeverity},
+    translation_file::{self, TranslationFile, TranslationFileError},
     ts_file::TSFile,
 };
-use walkdir::{DirEntry, WalkDir};
 
 /// Handle those damn translations...
 #[derive(Parser, Debug)]
@@ -15,191 +18,459 @@ struct Args {
     /// Root directory to search from
     #[arg(short, long, default_value = ".")]
     root_dir: PathBuf,
-    /// Path to English translation file
-    #[arg(short, long)]
-    en_file: PathBuf,
-    /// Path to Swedish translation file
-    #[arg(short, long)]
-    sv_file: PathBuf,
-    /// Path to key ignore unused file
+    /// Path to a locale's translation file (repeatable, e.g. `--locale en.properties --locale sv.properties`)
+    #[arg(short, long = "locale")]
+    locales: Vec<PathBuf>,
+    /// Directory containing one translation file per locale, used instead of repeated --locale
+    #[arg(long)]
+    locale_dir: Option<PathBuf>,
+    /// Which locale is authoritative; defaults to the first locale given
+    #[arg(long)]
+    reference_locale: Option<PathBuf>,
+    /// Path to key ignore unused file (shorthand for an `unused-key` allowlist; prefer --config)
     #[arg(short, long)]
     ignore_file: Option<PathBuf>,
+    /// Path to a rules config TOML file, falls back to `.tlcheckrc` in --root-dir
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Human)]
+    format: OutputFormat,
+    /// Remove unused keys from the translation files instead of just reporting them; only
+    /// applies while the `unused-key` rule is at `error` level
+    #[arg(long)]
+    fix: bool,
+    /// When fixing, also sort the remaining keys alphabetically
+    #[arg(long)]
+    sort_keys: bool,
+    /// Glob of paths to skip, in addition to .gitignore rules (repeatable)
+    #[arg(long = "exclude")]
+    excludes: Vec<String>,
+    /// Glob of paths to scan; when given, only matching paths are scanned (repeatable)
+    #[arg(long = "include")]
+    includes: Vec<String>,
+}
+
+/// How the check results should be reported.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// Colored, human-readable text on stdout (the default).
+    Human,
+    /// A single JSON document listing every `Diagnostic`.
+    Json,
+    /// A SARIF 2.1.0 log, for consumption by GitHub code scanning.
+    Sarif,
 }
 
 static EXTENSIONS_TO_SEARCH: [&str; 2] = ["ts", "tsx"];
 
 fn main() {
     let args = Args::parse();
-    println!("\n{}\n", style("Checking translations...").blue().bold());
+    let human = args.format == OutputFormat::Human;
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut errored_rules: HashSet<Rule> = HashSet::new();
+
+    if human {
+        println!("\n{}\n", style("Checking translations...").blue().bold());
+    }
 
-    // Try to open the translation files
-    let en_translation_file = TranslationFile::new(args.en_file);
-    let sv_translation_file = TranslationFile::new(args.sv_file);
-    match (&en_translation_file, &sv_translation_file) {
-        (Err(err), _) | (_, Err(err)) => {
+    let rules = RulesConfig::load(args.config.as_deref(), &args.root_dir).unwrap_or_else(|err| {
+        eprintln!("failed to load rules config: {err}");
+        std::process::exit(1);
+    });
+
+    // A key ignore-listed for unused-key via --ignore-file, merged into the config allowlist.
+    let legacy_unused_allow: Vec<String> = if let Some(ignore_file) = &args.ignore_file {
+        std::fs::read_to_string(ignore_file)
+            .unwrap_or_else(|err| {
+                println!(
+                    "{}{}",
+                    style("ERROR").red().bold(),
+                    style(format!(
+                        ": failed to read --ignore-file {}: {err}",
+                        ignore_file.display()
+                    ))
+                    .bold()
+                );
+                std::process::exit(1);
+            })
+            .lines()
+            .map(|line| line.trim().to_string())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let is_allowed = |rule: Rule, key: &str| {
+        rules.is_allowed(rule, key)
+            || (rule == Rule::UnusedKey && legacy_unused_allow.iter().any(|k| k == key))
+    };
+
+    // Discover every locale, either from --locale-dir or from repeated --locale flags.
+    let mut locale_files = if let Some(dir) = &args.locale_dir {
+        translation_file::load_locale_dir(dir).unwrap_or_else(|err| {
             println!(
                 "{}{}",
                 style("ERROR").red().bold(),
-                style(format!(": {}", err)).bold()
+                style(format!(": {err}")).bold()
             );
             std::process::exit(1);
-        }
-        (Ok(en_translation_file), Ok(sv_translation_file)) => {
-            if let Err((en_errors, sv_errors)) =
-                en_translation_file.is_compatible_with(sv_translation_file)
-            {
-                for error in en_errors.iter().chain(sv_errors.iter()) {
-                    match error {
-                        TranslationFileError::MissingKey { key, missing_in } => {
-                            println!(
-                                "{} key {} not found in {}",
-                                style("[MISSING]").yellow().bold(),
-                                style(key).bold(),
-                                style(missing_in.to_str().unwrap()).italic()
-                            );
-                        }
-                        TranslationFileError::EmptyValue(key) => {
-                            println!(
-                                "{} key {} seems to be empty",
-                                style("[EMPTY]").yellow().bold(),
-                                style(key).bold()
-                            );
-                        }
-                        _ => {}
-                    }
-                }
+        })
+    } else {
+        args.locales
+            .iter()
+            .cloned()
+            .map(TranslationFile::new)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_else(|err| {
+                println!(
+                    "{}{}",
+                    style("ERROR").red().bold(),
+                    style(format!(": {err}")).bold()
+                );
+                std::process::exit(1);
+            })
+    };
+
+    if locale_files.len() < 2 {
+        println!(
+            "{}{}",
+            style("ERROR").red().bold(),
+            style(": at least 2 locales are required, pass --locale twice or use --locale-dir")
+                .bold()
+        );
+        std::process::exit(1);
+    }
 
+    let reference_index = match &args.reference_locale {
+        Some(path) => locale_files
+            .iter()
+            .position(|file| &file.path == path)
+            .unwrap_or_else(|| {
                 println!(
                     "{}{}",
                     style("ERROR").red().bold(),
-                    style(": translation files are not compatible, see problems above").bold()
+                    style(format!(
+                        ": --reference-locale {} is not one of the given locales",
+                        path.display()
+                    ))
+                    .bold()
                 );
                 std::process::exit(1);
+            }),
+        None => 0,
+    };
+    let reference = locale_files.remove(reference_index);
+    let other_locales = locale_files;
+
+    // Check every locale against the reference for missing/extra keys and empty values.
+    if let Err(groups) = reference.is_compatible_with_all(&other_locales) {
+        for (_, errors) in &groups {
+            for error in errors {
+                match error {
+                    TranslationFileError::MissingKey { key, missing_in } => {
+                        report(
+                            &rules,
+                            &mut diagnostics,
+                            &mut errored_rules,
+                            Rule::MissingKey,
+                            key,
+                            &is_allowed,
+                            || {
+                                if human {
+                                    println!(
+                                        "{} key {} not found in {}",
+                                        style("[MISSING]").yellow().bold(),
+                                        style(key).bold(),
+                                        style(missing_in.to_str().unwrap()).italic()
+                                    );
+                                }
+                                Diagnostic::missing_key(
+                                    diag_severity(rules.severity(Rule::MissingKey)),
+                                    key,
+                                    missing_in,
+                                )
+                            },
+                        );
+                    }
+                    TranslationFileError::EmptyValue { key, file } => {
+                        report(
+                            &rules,
+                            &mut diagnostics,
+                            &mut errored_rules,
+                            Rule::EmptyValue,
+                            key,
+                            &is_allowed,
+                            || {
+                                if human {
+                                    println!(
+                                        "{} key {} seems to be empty in {}",
+                                        style("[EMPTY]").yellow().bold(),
+                                        style(key).bold(),
+                                        style(file.to_str().unwrap()).italic()
+                                    );
+                                }
+                                Diagnostic::empty_value(
+                                    diag_severity(rules.severity(Rule::EmptyValue)),
+                                    key,
+                                    file,
+                                )
+                            },
+                        );
+                    }
+                }
             }
         }
     }
 
-    // Test against all TS files in the root directory
-    let walker = WalkDir::new(args.root_dir)
-        .into_iter()
-        // Exclude node_modules
-        .filter_entry(|e| !is_node_modules(e))
+    // Test against all TS files in the root directory, honoring .gitignore/.ignore and the
+    // user-provided --include/--exclude globs
+    let mut overrides = OverrideBuilder::new(&args.root_dir);
+    for include in &args.includes {
+        overrides.add(include).unwrap_or_else(|err| {
+            eprintln!("invalid --include glob {include}: {err}");
+            std::process::exit(1);
+        });
+    }
+    for exclude in &args.excludes {
+        overrides
+            .add(&format!("!{exclude}"))
+            .unwrap_or_else(|err| {
+                eprintln!("invalid --exclude glob {exclude}: {err}");
+                std::process::exit(1);
+            });
+    }
+    let overrides = overrides.build().unwrap_or_else(|err| {
+        eprintln!("invalid --include/--exclude globs: {err}");
+        std::process::exit(1);
+    });
+
+    let candidate_paths: Vec<PathBuf> = WalkBuilder::new(&args.root_dir)
+        .overrides(overrides)
+        .build()
         // Filter out any non-accessible files
-        .filter_map(|e| e.ok());
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| EXTENSIONS_TO_SEARCH.contains(&ext))
+        })
+        .collect();
 
-    let mut used_keys = HashSet::new();
-    for entry in walker {
-        let path = entry.path();
-        if path.is_file() {
-            if let Some(ext) = path.extension() {
-                if EXTENSIONS_TO_SEARCH.contains(&ext.to_str().unwrap()) {
-                    let mut ts_file = TSFile::new(path);
-
-                    let formatted_message_keys = ts_file
-                        .find_formatted_message_usages()
-                        .into_iter()
-                        .map(|(_, key)| key)
-                        .collect::<Vec<_>>();
-                    let format_message_keys = ts_file
-                        .find_format_message_usages()
-                        .into_iter()
-                        .map(|(_, key)| key)
-                        .collect::<Vec<_>>();
-                    let misc_usages = ts_file
-                        .find_misc_usages()
-                        .into_iter()
-                        .map(|(_, key)| key)
-                        .collect::<Vec<_>>();
-
-                    // Insert all keys into the set
-                    used_keys.extend(format_message_keys);
-                    used_keys.extend(formatted_message_keys);
-                    used_keys.extend(misc_usages);
-                }
+    let used_keys = scan_used_keys(&candidate_paths);
+
+    // Check that all usages are valid against the reference locale
+    let mut sorted_used_keys: Vec<&String> = used_keys.iter().collect();
+    sorted_used_keys.sort();
+    sorted_used_keys.iter().for_each(|key| {
+        if !reference.entries.contains_key(key.as_str()) {
+            report(
+                &rules,
+                &mut diagnostics,
+                &mut errored_rules,
+                Rule::InvalidKey,
+                key,
+                &is_allowed,
+                || {
+                    if human {
+                        println!(
+                            "{} key {} does not exist!",
+                            style("[INVALID]").yellow().bold(),
+                            style(key).bold(),
+                        );
+                    }
+                    Diagnostic::invalid_key(
+                        diag_severity(rules.severity(Rule::InvalidKey)),
+                        key,
+                        &reference.path,
+                    )
+                },
+            );
+        }
+    });
+
+    // Check that all keys in the reference locale are used
+    let mut unused_keys = Vec::new();
+    let mut sorted_entries: Vec<(&String, &String)> = reference.entries.iter().collect();
+    sorted_entries.sort_by_key(|(key, _)| (*key).clone());
+    sorted_entries.into_iter().for_each(|(key, value)| {
+        if !used_keys.contains(key) && !is_allowed(Rule::UnusedKey, key) {
+            // Only feed --fix keys the config actually flags as an error; `unused-key =
+            // "warn"`/`"off"` are both gradual-adoption settings, not "delete these anyway".
+            if rules.severity(Rule::UnusedKey) == RuleSeverity::Error {
+                unused_keys.push(key.clone());
             }
+            report(
+                &rules,
+                &mut diagnostics,
+                &mut errored_rules,
+                Rule::UnusedKey,
+                key,
+                &is_allowed,
+                || {
+                    if human {
+                        println!(
+                            "{} key {}={}",
+                            style("[UNUSED]").yellow().bold(),
+                            style(key).bold(),
+                            style(format!("\"{}\"", value)).italic(),
+                        );
+                    }
+                    Diagnostic::unused_key(
+                        diag_severity(rules.severity(Rule::UnusedKey)),
+                        key,
+                        value,
+                        &reference.path,
+                    )
+                },
+            );
         }
-    }
+    });
 
-    // Check that all usages are valid
-    let mut invalid_usages = Vec::new();
+    if args.fix && !unused_keys.is_empty() {
+        let keys_to_remove: HashSet<String> = unused_keys.iter().cloned().collect();
+        for locale in std::iter::once(&reference).chain(other_locales.iter()) {
+            let fix_report =
+                autofix::remove_unused_keys(&locale.path, &keys_to_remove, args.sort_keys)
+                    .unwrap_or_else(|err| {
+                        eprintln!("failed to fix {}: {}", locale.path.display(), err);
+                        std::process::exit(1);
+                    });
+            if human {
+                println!(
+                    "{}{}",
+                    style("FIXED").green().bold(),
+                    style(format!(
+                        ": removed {} keys from {}",
+                        fix_report.removed,
+                        locale.path.display()
+                    ))
+                    .bold()
+                );
+            }
+        }
+    }
 
-    let entries = en_translation_file.as_ref().unwrap().entries.clone();
-    used_keys.iter().for_each(|key| {
-        if !entries.contains_key(key.as_str()) {
+    // One aggregated line per rule that fired, instead of bailing out on the first category.
+    if human {
+        for rule in Rule::ALL {
+            let count = diagnostics.iter().filter(|d| d.rule == rule.name()).count();
+            if count == 0 {
+                continue;
+            }
+            let label = if errored_rules.contains(&rule) {
+                style("ERROR").red().bold()
+            } else {
+                style("WARN").yellow().bold()
+            };
             println!(
-                "{} key {} does not exist!",
-                style("[INVALID]").yellow().bold(),
-                style(key).bold(),
+                "{}{}",
+                label,
+                style(format!(": {count} {} finding(s)", rule.name())).bold()
             );
-            invalid_usages.push(key.clone());
         }
-    });
+    }
+
+    if !human {
+        print_and_exit(&diagnostics, !errored_rules.is_empty(), args.format);
+    }
 
-    if !invalid_usages.is_empty() {
+    if errored_rules.is_empty() {
         println!(
             "{}{}",
-            style("ERROR").red().bold(),
-            style(format!(": {} invalid key usages!", invalid_usages.len())).bold(),
+            style("SUCCESS").green().bold(),
+            style(": great translations!").bold()
         );
+    } else {
         std::process::exit(1);
     }
+}
 
-    // Check that all keys are used
-    let ignore_unused_keys = if let Some(ignore_file) = args.ignore_file {
-        let ignore_file = std::fs::read_to_string(ignore_file).unwrap();
-        ignore_file
-            .lines()
-            .map(|line| line.trim().to_string())
-            .collect::<Vec<_>>()
-    } else {
-        Vec::new()
-    };
+fn diag_severity(severity: RuleSeverity) -> diagnostics::Severity {
+    match severity {
+        RuleSeverity::Error => diagnostics::Severity::Error,
+        RuleSeverity::Warn | RuleSeverity::Off => diagnostics::Severity::Warning,
+    }
+}
 
-    let mut unused_keys = Vec::new();
-    en_translation_file
-        .unwrap()
-        .entries
-        .iter()
-        .for_each(|(key, value)| {
-            if !used_keys.contains(key) && !ignore_unused_keys.contains(key) {
-                println!(
-                    "{} key {}={}",
-                    style("[UNUSED]").yellow().bold(),
-                    style(key).bold(),
-                    style(format!("\"{}\"", value)).italic(),
-                );
-                unused_keys.push(key.clone());
-            }
-        });
+/// Records a single finding for `rule`, unless the key is allowlisted or the rule is off.
+#[allow(clippy::too_many_arguments)]
+fn report(
+    rules: &RulesConfig,
+    diagnostics: &mut Vec<Diagnostic>,
+    errored_rules: &mut HashSet<Rule>,
+    rule: Rule,
+    key: &str,
+    is_allowed: &impl Fn(Rule, &str) -> bool,
+    build: impl FnOnce() -> Diagnostic,
+) {
+    if is_allowed(rule, key) || rules.severity(rule) == RuleSeverity::Off {
+        return;
+    }
+    if rules.severity(rule) == RuleSeverity::Error {
+        errored_rules.insert(rule);
+    }
+    diagnostics.push(build());
+}
 
-    if !unused_keys.is_empty() {
-        println!(
-            "{}{} {}",
-            style("ERROR").red().bold(),
-            style(format!(": {} unused keys found!", unused_keys.len(),)).bold(),
-            style(format!("({} keys ignored)", ignore_unused_keys.len())).italic()
-        );
-        println!(
-            "{}",
-            style("Unused keys should be removed from the translation files if they really are unused.").italic()
-        );
-        println!(
-            "{}",
-            style(
-                "If they are used (false positive), add them to the ignore file (--ignore-file)."
-            )
-            .italic()
-        );
-        std::process::exit(1);
+/// Prints the collected diagnostics in the requested non-human format and exits with a status
+/// code reflecting whether any error-level rule fired.
+fn print_and_exit(diags: &[Diagnostic], any_errors: bool, format: OutputFormat) -> ! {
+    let rendered = match format {
+        OutputFormat::Json => diagnostics::to_json(diags),
+        OutputFormat::Sarif => diagnostics::to_sarif(diags),
+        OutputFormat::Human => unreachable!("human output does not go through print_and_exit"),
     }
+    .expect("diagnostics are always serializable");
+    println!("{rendered}");
+
+    std::process::exit(if any_errors { 1 } else { 0 });
+}
+
+/// Scans `paths` for translation key usages, spreading the work across a pool of scoped
+/// worker threads. Each thread parses its share of files independently and builds a local
+/// set of used keys; the sets are merged once every thread has finished.
+fn scan_used_keys(paths: &[PathBuf]) -> HashSet<String> {
+    let worker_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(paths.len().max(1));
+    let chunk_size = paths.len().div_ceil(worker_count).max(1);
 
-    println!(
-        "{}{}",
-        style("SUCCESS").green().bold(),
-        style(": great translations!").bold()
-    );
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(|| scan_chunk(chunk)))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("scan worker panicked"))
+            .collect()
+    })
 }
 
-fn is_node_modules(entry: &DirEntry) -> bool {
-    entry.file_name() == "node_modules"
+/// Parses every `.ts`/`.tsx` file in `chunk` and returns the set of translation keys used.
+fn scan_chunk(chunk: &[PathBuf]) -> HashSet<String> {
+    let mut used_keys = HashSet::new();
+    for path in chunk {
+        let mut ts_file = TSFile::new(path);
+
+        let formatted_message_keys = ts_file
+            .find_formatted_message_usages()
+            .into_iter()
+            .map(|(_, key)| key);
+        let format_message_keys = ts_file
+            .find_format_message_usages()
+            .into_iter()
+            .map(|(_, key)| key);
+        let misc_usages = ts_file.find_misc_usages().into_iter().map(|(_, key)| key);
+
+        used_keys.extend(format_message_keys);
+        used_keys.extend(formatted_message_keys);
+        used_keys.extend(misc_usages);
+    }
+    used_keys
 }
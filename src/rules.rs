@@ -0,0 +1,96 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::Deserialize;
+
+/// The checks `tl-check` can run, each independently configurable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    MissingKey,
+    EmptyValue,
+    InvalidKey,
+    UnusedKey,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 4] = [
+        Rule::MissingKey,
+        Rule::EmptyValue,
+        Rule::InvalidKey,
+        Rule::UnusedKey,
+    ];
+
+    /// The name used to refer to this rule in config files and diagnostics, e.g. `missing-key`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::MissingKey => "missing-key",
+            Rule::EmptyValue => "empty-value",
+            Rule::InvalidKey => "invalid-key",
+            Rule::UnusedKey => "unused-key",
+        }
+    }
+}
+
+/// How a rule's findings should be treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[default]
+    Error,
+    Warn,
+    Off,
+}
+
+/// Rule severities and per-rule allowlists, loaded from a TOML config file.
+///
+/// ```toml
+/// [rules]
+/// unused-key = "warn"
+/// missing-key = "error"
+///
+/// [allow]
+/// unused-key = ["some.legacy.key"]
+/// empty-value = ["placeholder.key"]
+/// ```
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct RulesConfig {
+    rules: HashMap<String, Severity>,
+    allow: HashMap<String, Vec<String>>,
+}
+
+impl RulesConfig {
+    /// Loads the config from `config_path` if given, otherwise falls back to a `.tlcheckrc`
+    /// in `root_dir`. Returns the default (all rules at `error`, no allowlists) if neither an
+    /// explicit `config_path` nor the `.tlcheckrc` fallback exists; an explicit `config_path`
+    /// that doesn't exist is an error rather than a silent fallback to defaults.
+    pub fn load(
+        config_path: Option<&Path>,
+        root_dir: &Path,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = match config_path {
+            Some(path) => path.to_path_buf(),
+            None => root_dir.join(".tlcheckrc"),
+        };
+
+        if !path.exists() {
+            if config_path.is_some() {
+                return Err(format!("config file {} does not exist", path.display()).into());
+            }
+            return Ok(Self::default());
+        }
+
+        let contents = std::fs::read_to_string(&path)?;
+        let config = toml::from_str(&contents)?;
+        Ok(config)
+    }
+
+    pub fn severity(&self, rule: Rule) -> Severity {
+        self.rules.get(rule.name()).copied().unwrap_or_default()
+    }
+
+    pub fn is_allowed(&self, rule: Rule, key: &str) -> bool {
+        self.allow
+            .get(rule.name())
+            .is_some_and(|keys| keys.iter().any(|allowed| allowed == key))
+    }
+}
@@ -0,0 +1,160 @@
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A single parsed translation file, e.g. `en.properties`.
+#[derive(Debug, Clone)]
+pub struct TranslationFile {
+    pub path: PathBuf,
+    pub entries: HashMap<String, String>,
+}
+
+/// A single way in which two translation files can disagree.
+#[derive(Debug, Clone)]
+pub enum TranslationFileError {
+    /// `key` exists in one file but not in `missing_in`.
+    MissingKey { key: String, missing_in: PathBuf },
+    /// `key`'s value is empty or only whitespace, in `file`.
+    EmptyValue { key: String, file: PathBuf },
+}
+
+impl fmt::Display for TranslationFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranslationFileError::MissingKey { key, missing_in } => {
+                write!(f, "key {key} not found in {}", missing_in.display())
+            }
+            TranslationFileError::EmptyValue { key, file } => {
+                write!(f, "key {key} seems to be empty in {}", file.display())
+            }
+        }
+    }
+}
+
+impl TranslationFile {
+    /// Reads and parses a `key=value` translation file from disk.
+    pub fn new(path: PathBuf) -> io::Result<Self> {
+        let contents = fs::read_to_string(&path)?;
+        let entries = contents
+            .lines()
+            .filter_map(|line| {
+                let trimmed = line.trim();
+                if trimmed.is_empty() || trimmed.starts_with('#') {
+                    return None;
+                }
+                trimmed
+                    .split_once('=')
+                    .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            })
+            .collect();
+
+        Ok(Self { path, entries })
+    }
+
+    /// Checks `self` and `other` for keys missing on either side and for empty values in
+    /// either file. Returns the two per-file error lists (self's, then other's) when they
+    /// disagree.
+    pub fn is_compatible_with(
+        &self,
+        other: &TranslationFile,
+    ) -> Result<(), (Vec<TranslationFileError>, Vec<TranslationFileError>)> {
+        let mut self_errors = missing_keys(self, other);
+        self_errors.extend(empty_values(self));
+        let mut other_errors = missing_keys(other, self);
+        other_errors.extend(empty_values(other));
+
+        if self_errors.is_empty() && other_errors.is_empty() {
+            Ok(())
+        } else {
+            Err((self_errors, other_errors))
+        }
+    }
+
+    /// Checks `self` (the reference locale) against every file in `others`, returning one
+    /// error group per incompatible file. `self`'s own empty values are reported once, under
+    /// its own path, rather than once per comparison.
+    pub fn is_compatible_with_all(
+        &self,
+        others: &[TranslationFile],
+    ) -> Result<(), Vec<(PathBuf, Vec<TranslationFileError>)>> {
+        let mut groups: Vec<(PathBuf, Vec<TranslationFileError>)> = Vec::new();
+
+        let own_empty_values = empty_values(self);
+        if !own_empty_values.is_empty() {
+            groups.push((self.path.clone(), own_empty_values));
+        }
+
+        for other in others {
+            let mut errors = missing_keys(self, other);
+            errors.extend(missing_keys(other, self));
+            errors.extend(empty_values(other));
+            if !errors.is_empty() {
+                groups.push((other.path.clone(), errors));
+            }
+        }
+
+        if groups.is_empty() {
+            Ok(())
+        } else {
+            Err(groups)
+        }
+    }
+}
+
+/// Keys present in `a` that are missing from `b`, sorted by key for deterministic output.
+fn missing_keys(a: &TranslationFile, b: &TranslationFile) -> Vec<TranslationFileError> {
+    let mut keys: Vec<&String> = a
+        .entries
+        .keys()
+        .filter(|key| !b.entries.contains_key(key.as_str()))
+        .collect();
+    keys.sort();
+    keys.into_iter()
+        .map(|key| TranslationFileError::MissingKey {
+            key: key.clone(),
+            missing_in: b.path.clone(),
+        })
+        .collect()
+}
+
+/// `file`'s own entries whose value is empty or only whitespace, sorted by key for
+/// deterministic output.
+fn empty_values(file: &TranslationFile) -> Vec<TranslationFileError> {
+    let mut entries: Vec<(&String, &String)> = file
+        .entries
+        .iter()
+        .filter(|(_, value)| value.trim().is_empty())
+        .collect();
+    entries.sort_by_key(|(key, _)| *key);
+    entries
+        .into_iter()
+        .map(|(key, _)| TranslationFileError::EmptyValue {
+            key: key.clone(),
+            file: file.path.clone(),
+        })
+        .collect()
+}
+
+/// Extension used by translation files, e.g. `en.properties`.
+const LOCALE_EXTENSION: &str = "properties";
+
+/// Loads every `.properties` translation file directly inside `dir` (non-recursive). Other
+/// files (`.DS_Store`, stray notes, etc.) are skipped rather than mistaken for locales.
+pub fn load_locale_dir(dir: &Path) -> io::Result<Vec<TranslationFile>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_locale_file = path.is_file()
+            && path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext == LOCALE_EXTENSION);
+        if is_locale_file {
+            files.push(TranslationFile::new(path)?);
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}